@@ -1,5 +1,3 @@
-use std::usize;
-
 use anyhow::Result;
 use itertools::Itertools;
 
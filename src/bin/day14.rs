@@ -1,8 +1,10 @@
-use anyhow::Result;
-use aoc::{Grid, Position};
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use aoc::{Direction, Grid, GridCell, Position};
 use itertools::Itertools;
 
-#[derive(Default, PartialEq, Clone)]
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
 enum RockType {
     Rounded,
     Cube,
@@ -10,56 +12,40 @@ enum RockType {
     Empty,
 }
 
-impl std::fmt::Debug for RockType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            RockType::Rounded => write!(f, "{}", 'O'),
-            RockType::Cube => write!(f, "{}", '#'),
-            RockType::Empty => write!(f, "{}", '.'),
+impl GridCell for RockType {
+    fn from_char(ch: char) -> Result<Self> {
+        match ch {
+            'O' => Ok(RockType::Rounded),
+            '#' => Ok(RockType::Cube),
+            '.' => Ok(RockType::Empty),
+            _ => Err(anyhow!("not a rock")),
         }
     }
-}
 
-impl From<char> for RockType {
-    fn from(value: char) -> Self {
-        match value {
-            'O' => RockType::Rounded,
-            '#' => RockType::Cube,
-            _ => RockType::Empty,
+    fn to_char(&self) -> char {
+        match self {
+            RockType::Rounded => 'O',
+            RockType::Cube => '#',
+            RockType::Empty => '.',
         }
     }
 }
 
 trait RockGrid {
-    fn promote(&mut self, current: &Position, direction: &Position) -> Option<Position>;
-    fn tilt(&mut self, direction: &Position);
+    fn tilt(&mut self, direction: &Direction);
     fn get_total_load(&self) -> usize;
     fn cycle(&mut self);
 }
 
 impl RockGrid for Grid<RockType> {
-    fn promote(&mut self, current: &Position, direction: &Position) -> Option<Position> {
-        let upper = direction + current;
-        if !self.is_valid_pos(&upper) {
-            return None;
-        }
-        match self.get(&upper) {
-            RockType::Empty => {
-                *self.get_mut(current) = RockType::Empty;
-                *self.get_mut(&upper) = RockType::Rounded;
-                Some(upper)
-            }
-            _ => None,
-        }
-    }
-
-    fn tilt(&mut self, direction: &Position) {
-        let rows = if direction.row <= 0 {
+    fn tilt(&mut self, direction: &Direction) {
+        let offset = direction.offset();
+        let rows = if offset.row <= 0 {
             (0..self.rows).collect_vec()
         } else {
             (0..self.rows).rev().collect_vec()
         };
-        let cols = if direction.col <= 0 {
+        let cols = if offset.col <= 0 {
             (0..self.cols).collect_vec()
         } else {
             (0..self.cols).rev().collect_vec()
@@ -67,36 +53,28 @@ impl RockGrid for Grid<RockType> {
 
         for row in rows {
             for col in cols.clone() {
-                let mut current = aoc::pos!(row, col);
-                match self.get(&current) {
-                    RockType::Rounded => {
-                        while let Some(new_pos) = self.promote(&current, direction) {
-                            current = new_pos;
-                        }
-                    }
-                    _ => (),
+                let current = aoc::pos!(row, col);
+                if self[current] == RockType::Rounded {
+                    let landing = self.cast(current, direction, |rock| rock != &RockType::Empty);
+                    self[current] = RockType::Empty;
+                    self[landing.last] = RockType::Rounded;
                 }
             }
         }
     }
 
     fn get_total_load(&self) -> usize {
-        let mut total_load = 0;
-
-        for (pos, rock) in self.nodes.iter() {
-            match rock {
-                RockType::Rounded => total_load += self.rows - pos.row as usize,
-                _ => (),
-            }
-        }
-        total_load
+        self.positions_of(&RockType::Rounded)
+            .map(|pos| self.rows - pos.row as usize)
+            .sum()
     }
 
     fn cycle(&mut self) {
-        self.tilt(&aoc::pos!(-1, 0)); // tilt north
-        self.tilt(&aoc::pos!(0, -1)); // tilt west
-        self.tilt(&aoc::pos!(1, 0)); // tilt south
-        self.tilt(&aoc::pos!(0, 1)); // tilt east
+        // tilting north and rotating clockwise brings west, south then east to the top
+        for _ in 0..4 {
+            self.tilt(&Direction::North);
+            *self = self.rotate_cw();
+        }
     }
 }
 
@@ -104,23 +82,21 @@ fn main() -> Result<()> {
     let mut grid: Grid<RockType> = include_str!("../../data/day14.input").parse()?;
     let mut grid_2: Grid<RockType> = include_str!("../../data/day14.input").parse()?;
 
-    grid.tilt(&aoc::pos!(-1, 0));
+    grid.tilt(&Direction::North);
 
     println!("Part 1: {}", grid.get_total_load());
 
     // part 2
-    let mut cycles_states = Vec::new();
-    while !cycles_states.contains(&format!("{grid_2}")) {
-        cycles_states.push(format!("{grid_2}"));
+    // maps each state to the iteration it was first seen at
+    let mut cycles_states = HashMap::new();
+    while !cycles_states.contains_key(&grid_2) {
+        cycles_states.insert(grid_2.clone(), cycles_states.len());
         grid_2.cycle();
     }
 
     let len = cycles_states.len();
     println!("Found cycle in {len} iterations");
-    let cycle_start = cycles_states
-        .iter()
-        .position(|s| s == &format!("{grid_2}"))
-        .unwrap();
+    let cycle_start = cycles_states[&grid_2];
     println!("Cycle starts at {cycle_start} iteration");
     let cycle_length = len - cycle_start;
     println!("cycle length: {cycle_length}");
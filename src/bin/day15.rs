@@ -39,7 +39,7 @@ fn compute_hash(hash: &mut usize, ch: char) {
     let ascii = (ch as u8) as usize;
     *hash += ascii;
     *hash *= 17;
-    *hash = *hash % 256;
+    *hash %= 256;
 }
 
 struct BoxMap {
@@ -107,12 +107,11 @@ fn main() -> Result<()> {
         "Part 2: {:?}",
         map.boxes
             .iter()
-            .filter(|(_, val)| val.len() != 0)
-            .map(|(box_no, lens)| lens
+            .filter(|(_, val)| !val.is_empty())
+            .flat_map(|(box_no, lens)| lens
                 .iter()
                 .enumerate()
-                .map(|(idx, l)| (box_no.clone() + 1) * (idx + 1) * l.focal_length.unwrap()))
-            .flatten()
+                .map(|(idx, l)| (*box_no + 1) * (idx + 1) * l.focal_length.unwrap()))
             .sum::<usize>()
     );
 
@@ -1,7 +1,7 @@
 use std::collections::{HashSet, VecDeque};
 
-use anyhow::Result;
-use aoc::{Grid, Position};
+use anyhow::{anyhow, Result};
+use aoc::{Direction, Grid, GridCell, Position};
 
 #[derive(Clone, Debug, Default)]
 enum Node {
@@ -13,33 +13,25 @@ enum Node {
     HSplitter,
 }
 
-#[derive(Hash, PartialEq, Clone, Eq)]
-enum Direction {
-    North,
-    South,
-    West,
-    East,
-}
-
-impl Direction {
-    fn get_offset(&self) -> Position {
-        match self {
-            Direction::North => aoc::pos!(-1, 0),
-            Direction::South => aoc::pos!(1, 0),
-            Direction::West => aoc::pos!(0, -1),
-            Direction::East => aoc::pos!(0, 1),
+impl GridCell for Node {
+    fn from_char(ch: char) -> Result<Self> {
+        match ch {
+            '/' => Ok(Node::RightMirror),
+            '\\' => Ok(Node::LeftMirror),
+            '-' => Ok(Node::HSplitter),
+            '|' => Ok(Node::VSplitter),
+            '.' => Ok(Node::Empty),
+            _ => Err(anyhow!("not part of the contraption")),
         }
     }
-}
 
-impl From<char> for Node {
-    fn from(value: char) -> Self {
-        match value {
-            '/' => Node::RightMirror,
-            '\\' => Node::LeftMirror,
-            '-' => Node::HSplitter,
-            '|' => Node::VSplitter,
-            _ => Node::Empty,
+    fn to_char(&self) -> char {
+        match self {
+            Node::RightMirror => '/',
+            Node::LeftMirror => '\\',
+            Node::HSplitter => '-',
+            Node::VSplitter => '|',
+            Node::Empty => '.',
         }
     }
 }
@@ -57,97 +49,42 @@ struct Contraption {
     visited: HashSet<VecPos>,
 }
 
-fn reflect(mirror: &Node, dir: &Direction) -> Direction {
-    match dir {
-        Direction::North => match mirror {
-            Node::RightMirror => Direction::East,
-            Node::LeftMirror => Direction::West,
-            _ => todo!(),
-        },
-        Direction::South => match mirror {
-            Node::RightMirror => Direction::West,
-            Node::LeftMirror => Direction::East,
-            _ => todo!(),
-        },
-
-        Direction::West => match mirror {
-            Node::RightMirror => Direction::South,
-            Node::LeftMirror => Direction::North,
-            _ => todo!(),
-        },
-
-        Direction::East => match mirror {
-            Node::RightMirror => Direction::North,
-            Node::LeftMirror => Direction::South,
-            _ => todo!(),
-        },
+fn reflect(mirror: &Node, dir: Direction) -> Direction {
+    let vertical = matches!(dir, Direction::North | Direction::South);
+    match mirror {
+        Node::RightMirror if vertical => dir.turn_right(),
+        Node::RightMirror => dir.turn_left(),
+        Node::LeftMirror if vertical => dir.turn_left(),
+        Node::LeftMirror => dir.turn_right(),
+        _ => unreachable!("{mirror:?} is not a mirror"),
     }
 }
 
 impl Contraption {
     fn shine(&mut self, pos: VecPos) {
-        if !self.grid.is_valid_pos(&pos.pos) {
+        let Some(node) = self.grid.try_get(&pos.pos) else {
             return;
-        }
-        let dir = pos.dir.clone();
-        let poses = match self.grid.get(&pos.pos) {
-            Node::Empty => vec![VecPos {
-                pos: pos.pos.clone() + &pos.dir.get_offset(),
-                dir: dir,
-            }],
-            Node::RightMirror => vec![VecPos {
-                pos: pos.pos.clone() + &reflect(&Node::RightMirror, &pos.dir).get_offset(),
-                dir: reflect(&Node::RightMirror, &pos.dir),
-            }],
-            Node::LeftMirror => vec![VecPos {
-                pos: pos.pos.clone() + &reflect(&Node::LeftMirror, &pos.dir).get_offset(),
-                dir: reflect(&Node::LeftMirror, &pos.dir),
-            }],
-            Node::VSplitter => match pos.dir {
-                Direction::North => vec![VecPos {
-                    pos: pos.pos.clone() + &pos.dir.get_offset(),
-                    dir: dir,
-                }],
-                Direction::South => vec![VecPos {
-                    pos: pos.pos.clone() + &pos.dir.get_offset(),
-                    dir: dir,
-                }],
-                _ => vec![
-                    VecPos {
-                        pos: pos.pos.clone() + &Direction::North.get_offset(),
-                        dir: Direction::North,
-                    },
-                    VecPos {
-                        pos: pos.pos.clone() + &Direction::South.get_offset(),
-                        dir: Direction::South,
-                    },
-                ],
-            },
-            Node::HSplitter => match pos.dir {
-                Direction::East => vec![VecPos {
-                    pos: pos.pos.clone() + &pos.dir.get_offset(),
-                    dir: dir,
-                }],
-                Direction::West => vec![VecPos {
-                    pos: pos.pos.clone() + &pos.dir.get_offset(),
-                    dir: dir,
-                }],
-                _ => vec![
-                    VecPos {
-                        pos: pos.pos.clone() + &Direction::East.get_offset(),
-                        dir: Direction::East,
-                    },
-                    VecPos {
-                        pos: pos.pos.clone() + &Direction::West.get_offset(),
-                        dir: Direction::West,
-                    },
-                ],
-            },
         };
+        let dir = pos.dir;
+        let dirs = match node {
+            Node::RightMirror | Node::LeftMirror => vec![reflect(node, dir)],
+            // splitters send the beam out both sides
+            Node::VSplitter if matches!(dir, Direction::East | Direction::West) => {
+                vec![dir.turn_left(), dir.turn_right()]
+            }
+            Node::HSplitter if matches!(dir, Direction::North | Direction::South) => {
+                vec![dir.turn_left(), dir.turn_right()]
+            }
+            _ => vec![dir],
+        };
+        let here = pos.pos;
+        let poses = dirs.into_iter().map(|dir| VecPos {
+            pos: here + dir.offset(),
+            dir,
+        });
         self.visited.insert(pos);
         poses
-            .into_iter()
-            .filter(|posi| !self.visited.contains(&posi))
+            .filter(|posi| !self.visited.contains(posi))
             .for_each(|posi| self.queue.push_back(posi));
     }
 
@@ -158,11 +95,11 @@ impl Contraption {
     }
 
     fn energized(&self) -> usize {
+        let mut energized = self.grid.map(|_| false);
         self.visited
             .iter()
-            .map(|pos| pos.pos.clone())
-            .collect::<HashSet<Position>>()
-            .len()
+            .for_each(|pos| energized[pos.pos] = true);
+        energized.count(|lit| *lit)
     }
 }
 
@@ -28,39 +28,27 @@ impl PartialOrd for Node {
 }
 
 struct HeatBlock {
-    grid: Grid<char>,
+    grid: Grid<u8>,
     dest_pos: Position,
 }
 
 impl HeatBlock {
     fn get_heat(&self, pos: &Position) -> usize {
-        self.grid.get(pos).to_digit(10).unwrap() as usize
+        self.grid[*pos] as usize
     }
 
-    fn get_neighbours(&self, node: &Node, min: u8, max: u8) -> Vec<Node> {
-        let mut offsets = aoc::DIR_OFFSETS.clone();
-        // removing the reverse direction
-        match node.dir {
-            Direction::North => offsets.remove(&Direction::South),
-            Direction::South => offsets.remove(&Direction::North),
-            Direction::East => offsets.remove(&Direction::West),
-            Direction::West => offsets.remove(&Direction::East),
-        };
-
-        if node.step_length == max {
-            offsets.remove(&node.dir);
-        }
-
-        if node.step_length < min {
-            offsets = [(node.dir.clone(), offsets[&node.dir].clone())]
-                .into_iter()
-                .collect()
-        }
-
-        offsets
-            .drain()
-            .map(|(dir, off)| (dir, node.pos + &off))
-            .filter(|(_, pos)| self.grid.is_valid_pos(pos))
+    fn get_neighbours<'a>(
+        &'a self,
+        node: &'a Node,
+        min: u8,
+        max: u8,
+    ) -> impl Iterator<Item = Node> + 'a {
+        self.grid
+            .dir_neighbours(node.pos)
+            // removing the reverse direction
+            .filter(move |(dir, _)| dir != &node.dir.opposite())
+            .filter(move |(dir, _)| node.step_length < max || dir != &node.dir)
+            .filter(move |(dir, _)| node.step_length >= min || dir == &node.dir)
             .map(|(dir, pos)| Node {
                 step_length: if node.dir == dir {
                     node.step_length + 1
@@ -72,7 +60,6 @@ impl HeatBlock {
                 pos,
                 dir,
             })
-            .collect()
     }
 
     fn find_path(&self, min: u8, max: u8) -> usize {
@@ -109,7 +96,7 @@ impl HeatBlock {
 }
 
 fn main() -> Result<()> {
-    let grid: Grid<char> = include_str!("../../data/day17.input").parse()?;
+    let grid: Grid<u8> = include_str!("../../data/day17.input").parse()?;
     let dest_pos = aoc::pos!(grid.rows - 1, grid.cols - 1);
     let heat_block = HeatBlock { dest_pos, grid };
     println!("Part 1: {}", heat_block.find_path(0, 3));
@@ -1,7 +1,8 @@
 use std::str::FromStr;
 
 use anyhow::{anyhow, Result};
-use aoc::{Direction, Position};
+use aoc::{Direction, Notation, Position64};
+use itertools::Itertools;
 
 trait Instruction {
     fn dir(&self) -> &Direction;
@@ -45,7 +46,11 @@ impl FromStr for Instruction1 {
 
     fn from_str(s: &str) -> Result<Self> {
         let (dir, s) = s.split_once(" ").ok_or(anyhow!("Failed to parse"))?;
-        let dir = parse_dir(dir)?;
+        let dir = dir
+            .chars()
+            .exactly_one()
+            .map_err(|_| anyhow!("Failed to parse"))?;
+        let dir = Direction::from_notation(dir, Notation::Udlr)?;
         let (num, _) = s.split_once(" ").ok_or(anyhow!("Failed to parse"))?;
         let mag = num.parse()?;
         Ok(Self { dir, mag })
@@ -62,48 +67,30 @@ impl FromStr for Instruction2 {
         s.pop();
         s.remove(0);
         s.remove(0);
-        let dir = s.pop().unwrap();
-        let dir = match dir {
-            '0' => Ok(Direction::East),
-            '1' => Ok(Direction::South),
-            '2' => Ok(Direction::West),
-            '3' => Ok(Direction::North),
-            _ => Err(anyhow!("invalid direction")),
-        }?;
+        let dir = s.pop().ok_or(anyhow!("Failed to parse"))?;
+        let dir = Direction::from_notation(dir, Notation::Numeric)?;
         let mag = usize::from_str_radix(&s, 16)?;
         Ok(Instruction2 { dir, mag })
     }
 }
-fn parse_dir(s: &str) -> Result<Direction> {
-    match s {
-        "R" => Ok(Direction::East),
-        "L" => Ok(Direction::West),
-        "U" => Ok(Direction::North),
-        "D" => Ok(Direction::South),
-        _ => Err(anyhow!("invalid direction")),
-    }
-}
 
 fn shoelace<T: Instruction>(instructions: Vec<T>) -> usize {
-    let mut cursor = aoc::pos!(0, 0);
-    let mut points = vec![cursor.clone()];
+    let mut cursor = aoc::pos!(0, 0; i64);
+    let mut points = vec![cursor];
 
     let mut perimeter = 0;
     for instruction in instructions {
-        for _ in 0..instruction.mag() {
-            cursor = cursor + &aoc::DIR_OFFSETS[&instruction.dir()];
-            perimeter += 1
-        }
-        points.push(cursor.clone())
+        cursor += Position64::from(instruction.dir().offset()) * instruction.mag() as i64;
+        perimeter += instruction.mag();
+        points.push(cursor)
     }
 
-    let mut sum = 0.0_f64;
-    for idx in 0..points.len() - 1 {
-        sum += points[idx].col as f64 * points[idx + 1].row as f64;
-        sum -= points[idx + 1].col as f64 * points[idx].row as f64;
-    }
+    let sum: i64 = points
+        .windows(2)
+        .map(|pair| pair[0].col * pair[1].row - pair[1].col * pair[0].row)
+        .sum();
 
-    (sum.abs() as usize / 2) + (perimeter / 2) + 1
+    (sum.unsigned_abs() as usize / 2) + (perimeter / 2) + 1
 }
 
 fn main() -> Result<()> {
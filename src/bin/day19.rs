@@ -143,11 +143,11 @@ fn main() -> Result<()> {
             p.pop()?;
             Some(
                 p.split(",")
-                    .filter_map(|s| {
+                    .map(|s| {
                         let (field, num) = s.split_once("=").unwrap();
                         let field = field.chars().next().unwrap();
                         let num = num.parse::<usize>().unwrap();
-                        Some((field, num))
+                        (field, num)
                     })
                     .collect(),
             )
@@ -156,7 +156,7 @@ fn main() -> Result<()> {
 
     let accepted = parts
         .iter()
-        .filter_map(|part| {
+        .filter(|part| {
             let mut workflow = String::from("in");
             while let Status::Workflow(name) = workflows.get(&workflow).unwrap().process(part) {
                 workflow = name;
@@ -164,8 +164,8 @@ fn main() -> Result<()> {
             let status = workflows.get(&workflow).unwrap().process(part);
             match status {
                 Status::Workflow(_) => panic!("shouldn't be here"),
-                Status::Accepted => Some(part),
-                Status::Rejected => None,
+                Status::Accepted => true,
+                Status::Rejected => false,
             }
         })
         .collect_vec();
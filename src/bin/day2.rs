@@ -1,6 +1,6 @@
 use anyhow::{anyhow, Error, Result};
 use itertools::Itertools;
-use std::{str::FromStr, u8, usize};
+use std::str::FromStr;
 
 #[derive(Debug)]
 struct Set {
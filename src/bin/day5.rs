@@ -1,7 +1,7 @@
 use anyhow::{anyhow, Result};
 use itertools::Itertools;
 use rayon::prelude::*;
-use std::{str::FromStr, usize};
+use std::str::FromStr;
 
 struct Range {
     source_start: usize,
@@ -11,7 +11,7 @@ struct Range {
 
 impl Range {
     fn from_vec(data: Vec<usize>) -> Result<Self> {
-        if data.len() == 0 {
+        if data.is_empty() {
             return Err(anyhow!("empty list"));
         }
         Ok(Self::new(data[0], data[1], data[2]))
@@ -38,15 +38,10 @@ struct Map {
 
 impl Map {
     fn get_dest(&self, source: usize) -> usize {
-        if let Some(range) = self
-            .ranges
-            .iter()
-            .filter(|range| range.contains(source))
-            .next()
-        {
-            return range.get_dest(source);
+        if let Some(range) = self.ranges.iter().find(|range| range.contains(source)) {
+            range.get_dest(source)
         } else {
-            return source;
+            source
         }
     }
 }
@@ -99,9 +94,8 @@ fn main() -> Result<()> {
             let light = water_to_light.get_dest(water);
             let temp = light_to_temp.get_dest(light);
             let humidity = temp_to_humid.get_dest(temp);
-            let location = humid_to_location.get_dest(humidity);
 
-            location
+            humid_to_location.get_dest(humidity)
         })
         .collect_vec();
 
@@ -109,8 +103,7 @@ fn main() -> Result<()> {
 
     let new_seeds = seeds_1
         .chunks(2)
-        .map(|pair| (pair[0]..pair[0] + pair[1]).collect_vec())
-        .flatten()
+        .flat_map(|pair| (pair[0]..pair[0] + pair[1]).collect_vec())
         .collect_vec();
 
     println!("Number of seeds: {:?}", new_seeds.len());
@@ -125,9 +118,8 @@ fn main() -> Result<()> {
             let light = water_to_light.get_dest(water);
             let temp = light_to_temp.get_dest(light);
             let humidity = temp_to_humid.get_dest(temp);
-            let location = humid_to_location.get_dest(humidity);
 
-            location
+            humid_to_location.get_dest(humidity)
         })
         .collect();
 
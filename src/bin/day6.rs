@@ -31,14 +31,14 @@ fn main() -> Result<()> {
         .next()
         .unwrap()
         .chars()
-        .filter(|ch| ch.is_digit(10))
+        .filter(|ch| ch.is_ascii_digit())
         .collect::<String>()
         .parse::<usize>()?;
     let dist = data
         .next()
         .unwrap()
         .chars()
-        .filter(|ch| ch.is_digit(10))
+        .filter(|ch| ch.is_ascii_digit())
         .collect::<String>()
         .parse::<usize>()?;
 
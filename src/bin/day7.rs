@@ -25,7 +25,7 @@ fn get_rank(ch: char) -> usize {
     ranks.find(ch).expect("invalid character")
 }
 
-fn cmp_cards(me: &String, other: &String) -> Ordering {
+fn cmp_cards(me: &str, other: &str) -> Ordering {
     let me_ranked = me.chars().map(get_rank).collect_vec();
     let other_ranked = other.chars().map(get_rank).collect_vec();
     me_ranked.cmp(&other_ranked)
@@ -33,18 +33,18 @@ fn cmp_cards(me: &String, other: &String) -> Ordering {
 
 impl PartialOrd for Hand {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        let order = other.get_type().cmp(&self.get_type());
-        Some(match order {
-            std::cmp::Ordering::Less => order,
-            std::cmp::Ordering::Equal => cmp_cards(&self.val, &other.val),
-            std::cmp::Ordering::Greater => order,
-        })
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for Hand {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).expect("pls work")
+        let order = other.get_type().cmp(&self.get_type());
+        match order {
+            std::cmp::Ordering::Less => order,
+            std::cmp::Ordering::Equal => cmp_cards(&self.val, &other.val),
+            std::cmp::Ordering::Greater => order,
+        }
     }
 }
 
@@ -63,7 +63,7 @@ impl FromStr for Hand {
 impl Hand {
     fn get_type(&self) -> HandType {
         let cards = self.val.chars().collect_vec();
-        let set: HashSet<char> = HashSet::from_iter(cards.clone().into_iter());
+        let set: HashSet<char> = HashSet::from_iter(cards.clone());
 
         let is_joker = set.contains(&'J');
         let card_count = set
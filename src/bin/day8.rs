@@ -1,4 +1,4 @@
-use std::{collections::HashMap, str::FromStr, usize};
+use std::{collections::HashMap, str::FromStr};
 
 use anyhow::{anyhow, Result};
 use itertools::Itertools;
@@ -48,12 +48,12 @@ impl Node {
     }
 }
 
-fn parse_instructions(s: &str) -> Vec<Instruction> {
+fn parse_instructions(s: &str) -> Result<Vec<Instruction>> {
     s.chars()
         .map(|c| match c {
-            'R' => Instruction::Right,
-            'L' => Instruction::Left,
-            _ => panic!("invalid char"),
+            'R' => Ok(Instruction::Right),
+            'L' => Ok(Instruction::Left),
+            _ => Err(anyhow!("can only turn left or right, got {c:?}")),
         })
         .collect()
 }
@@ -62,7 +62,7 @@ fn traverse_network(
     mut cursor: String,
     num_zs: usize,
     network: &HashMap<String, Node>,
-    instructions: &Vec<Instruction>,
+    instructions: &[Instruction],
 ) -> usize {
     let mut step = 0;
     let mag = instructions.len();
@@ -97,7 +97,7 @@ fn gcd_of_two_numbers(a: usize, b: usize) -> usize {
 fn traverse_network_parrallel(
     cursors: Vec<String>,
     network: &HashMap<String, Node>,
-    instructions: &Vec<Instruction>,
+    instructions: &[Instruction],
 ) -> usize {
     let steps: Vec<usize> = cursors
         .par_iter()
@@ -109,7 +109,7 @@ fn traverse_network_parrallel(
 fn main() -> Result<()> {
     let s = include_str!("../../data/day8.input");
     let (instructions, s) = s.split_once("\n\n").unwrap();
-    let instructions = parse_instructions(instructions);
+    let instructions = parse_instructions(instructions)?;
 
     let network: HashMap<String, Node> = s
         .split("\n")
@@ -124,7 +124,7 @@ fn main() -> Result<()> {
     let nodes = network.keys().collect_vec();
     let start_nodes = nodes
         .iter()
-        .filter(|n| n.chars().last().unwrap() == 'A')
+        .filter(|n| n.ends_with('A'))
         .map(|s| s.to_string())
         .collect_vec();
     let step = traverse_network_parrallel(start_nodes, &network, &instructions);
@@ -1,25 +1,25 @@
 use anyhow::Result;
 use itertools::Itertools;
 
-fn compute_difference(seq: &Vec<i32>) -> Vec<i32> {
+fn compute_difference(seq: &[i32]) -> Vec<i32> {
     seq.windows(2).map(|nums| nums[1] - nums[0]).collect_vec()
 }
 
-fn process_history_back(history: &Vec<i32>) -> i32 {
+fn process_history_back(history: &[i32]) -> i32 {
     let diff = compute_difference(history);
     if diff.iter().filter(|el| el != &&0).count() == 0 {
-        return history.last().unwrap().clone();
+        *history.last().unwrap()
     } else {
-        return history.last().unwrap().clone() + process_history_back(&diff);
+        *history.last().unwrap() + process_history_back(&diff)
     }
 }
 
-fn process_history_forward(history: &Vec<i32>) -> i32 {
-    let diff = compute_difference(&history);
+fn process_history_forward(history: &[i32]) -> i32 {
+    let diff = compute_difference(history);
     if diff.iter().filter(|el| el != &&0).count() == 0 {
-        return history.first().unwrap().clone();
+        *history.first().unwrap()
     } else {
-        return history.first().unwrap().clone() - process_history_forward(&diff);
+        *history.first().unwrap() - process_history_forward(&diff)
     }
 }
 
@@ -33,17 +33,11 @@ fn main() -> Result<()> {
                 .filter_map(|num| num.parse::<i32>().ok())
                 .collect_vec()
         })
-        .filter(|history| history.len() != 0)
+        .filter(|history| !history.is_empty())
         .collect_vec();
-    let part_1: i32 = histories
-        .iter()
-        .map(|history| process_history_back(history))
-        .sum();
+    let part_1: i32 = histories.iter().map(|h| process_history_back(h)).sum();
     println!("Part 1: {part_1}");
-    let part_2: i32 = histories
-        .iter()
-        .map(|history| process_history_forward(history))
-        .sum();
+    let part_2: i32 = histories.iter().map(|h| process_history_forward(h)).sum();
     println!("Part 2: {part_2}");
     Ok(())
 }
@@ -0,0 +1,315 @@
+use std::{
+    fmt::Display,
+    ops::{BitAnd, BitOr, BitXor},
+};
+
+use crate::{pos, Direction, Grid, Position};
+
+const WORD: usize = u64::BITS as usize;
+
+// a grid of booleans packed into u64 words, kept both row by row and column by column
+// so whole rows or columns compare with a handful of word operations
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    pub rows: usize,
+    pub cols: usize,
+    row_words: usize,
+    col_words: usize,
+    // row r occupies row_bits[r * row_words..(r + 1) * row_words], bit c is column c
+    row_bits: Vec<u64>,
+    // same layout transposed, bit r of column c is row r
+    col_bits: Vec<u64>,
+}
+
+fn set_bit(words: &mut [u64], idx: usize, value: bool) {
+    let mask = 1 << (idx % WORD);
+    if value {
+        words[idx / WORD] |= mask;
+    } else {
+        words[idx / WORD] &= !mask;
+    }
+}
+
+fn diff_bits(a: &[u64], b: &[u64]) -> u32 {
+    a.iter().zip(b).map(|(a, b)| (a ^ b).count_ones()).sum()
+}
+
+impl BitGrid {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let row_words = cols.div_ceil(WORD);
+        let col_words = rows.div_ceil(WORD);
+        BitGrid {
+            rows,
+            cols,
+            row_words,
+            col_words,
+            row_bits: vec![0; rows * row_words],
+            col_bits: vec![0; cols * col_words],
+        }
+    }
+
+    // sets every cell where pred holds
+    pub fn from_grid<T>(grid: &Grid<T>, mut pred: impl FnMut(&T) -> bool) -> Self {
+        let mut bits = BitGrid::new(grid.rows, grid.cols);
+        for (pos, node) in grid.iter() {
+            if pred(node) {
+                bits.set(&pos, true);
+            }
+        }
+        bits
+    }
+
+    pub fn is_valid_pos(&self, pos: &Position) -> bool {
+        pos.row >= 0 && pos.row < self.rows as i32 && pos.col >= 0 && pos.col < self.cols as i32
+    }
+
+    pub fn get(&self, pos: &Position) -> bool {
+        assert!(self.is_valid_pos(pos), "{:?} is invalid", pos);
+        let (row, col) = (pos.row as usize, pos.col as usize);
+        self.row(row)[col / WORD] >> (col % WORD) & 1 == 1
+    }
+
+    pub fn set(&mut self, pos: &Position, value: bool) {
+        assert!(self.is_valid_pos(pos), "{:?} is invalid", pos);
+        let (row, col) = (pos.row as usize, pos.col as usize);
+        let row_words = self.row_words;
+        let col_words = self.col_words;
+        set_bit(
+            &mut self.row_bits[row * row_words..(row + 1) * row_words],
+            col,
+            value,
+        );
+        set_bit(
+            &mut self.col_bits[col * col_words..(col + 1) * col_words],
+            row,
+            value,
+        );
+    }
+
+    pub fn row(&self, row: usize) -> &[u64] {
+        assert!(row < self.rows, "row {row} is invalid");
+        &self.row_bits[row * self.row_words..(row + 1) * self.row_words]
+    }
+
+    pub fn col(&self, col: usize) -> &[u64] {
+        assert!(col < self.cols, "col {col} is invalid");
+        &self.col_bits[col * self.col_words..(col + 1) * self.col_words]
+    }
+
+    pub fn row_eq(&self, a: usize, b: usize) -> bool {
+        self.row(a) == self.row(b)
+    }
+
+    pub fn col_eq(&self, a: usize, b: usize) -> bool {
+        self.col(a) == self.col(b)
+    }
+
+    // number of cells that differ between two rows
+    pub fn row_diff(&self, a: usize, b: usize) -> u32 {
+        diff_bits(self.row(a), self.row(b))
+    }
+
+    pub fn col_diff(&self, a: usize, b: usize) -> u32 {
+        diff_bits(self.col(a), self.col(b))
+    }
+
+    pub fn count_ones(&self) -> u32 {
+        self.row_bits.iter().map(|word| word.count_ones()).sum()
+    }
+
+    fn rebuild_cols(&mut self) {
+        self.col_bits.iter_mut().for_each(|word| *word = 0);
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                if self.get(&pos!(row, col)) {
+                    set_bit(
+                        &mut self.col_bits[col * self.col_words..(col + 1) * self.col_words],
+                        row,
+                        true,
+                    );
+                }
+            }
+        }
+    }
+
+    // moves every cell one step in the direction, cells pushed off the edge are dropped
+    pub fn shift(&self, direction: &Direction) -> BitGrid {
+        let mut shifted = BitGrid::new(self.rows, self.cols);
+        let words = self.row_words;
+        match direction {
+            Direction::North if self.rows > 0 => {
+                shifted.row_bits[..(self.rows - 1) * words].copy_from_slice(&self.row_bits[words..])
+            }
+            Direction::South if self.rows > 0 => {
+                shifted.row_bits[words..].copy_from_slice(&self.row_bits[..(self.rows - 1) * words])
+            }
+            Direction::East => {
+                for row in 0..self.rows {
+                    let from = self.row(row);
+                    let to = &mut shifted.row_bits[row * words..(row + 1) * words];
+                    for idx in 0..words {
+                        let carry = if idx > 0 {
+                            from[idx - 1] >> (WORD - 1)
+                        } else {
+                            0
+                        };
+                        to[idx] = from[idx] << 1 | carry;
+                    }
+                    // drop whatever moved past the last column
+                    if !self.cols.is_multiple_of(WORD) {
+                        to[words - 1] &= (1 << (self.cols % WORD)) - 1;
+                    }
+                }
+            }
+            Direction::West => {
+                for row in 0..self.rows {
+                    let from = self.row(row);
+                    let to = &mut shifted.row_bits[row * words..(row + 1) * words];
+                    for idx in 0..words {
+                        let carry = if idx + 1 < words {
+                            from[idx + 1] << (WORD - 1)
+                        } else {
+                            0
+                        };
+                        to[idx] = from[idx] >> 1 | carry;
+                    }
+                }
+            }
+            _ => (),
+        }
+        shifted.rebuild_cols();
+        shifted
+    }
+
+    fn combine(&self, other: &BitGrid, op: impl Fn(u64, u64) -> u64) -> BitGrid {
+        assert!(
+            self.rows == other.rows && self.cols == other.cols,
+            "grids differ in size"
+        );
+        let zip = |a: &[u64], b: &[u64]| a.iter().zip(b).map(|(a, b)| op(*a, *b)).collect();
+        BitGrid {
+            row_bits: zip(&self.row_bits, &other.row_bits),
+            col_bits: zip(&self.col_bits, &other.col_bits),
+            ..self.clone()
+        }
+    }
+}
+
+impl BitXor for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        self.combine(rhs, |a, b| a ^ b)
+    }
+}
+
+impl BitAnd for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.combine(rhs, |a, b| a & b)
+    }
+}
+
+impl BitOr for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.combine(rhs, |a, b| a | b)
+    }
+}
+
+// set cells print as # and the rest as .
+impl Display for BitGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                let set = self.get(&pos!(row, col));
+                write!(f, "{}", if set { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a copy built one cell at a time, so its column words are known to be right
+    fn rebuilt(bits: &BitGrid) -> BitGrid {
+        let mut copy = BitGrid::new(bits.rows, bits.cols);
+        for row in 0..bits.rows {
+            for col in 0..bits.cols {
+                copy.set(&pos!(row, col), bits.get(&pos!(row, col)));
+            }
+        }
+        copy
+    }
+
+    fn with_set(rows: usize, cols: usize, cells: &[(usize, usize)]) -> BitGrid {
+        let mut bits = BitGrid::new(rows, cols);
+        for (row, col) in cells {
+            bits.set(&pos!(*row, *col), true);
+        }
+        bits
+    }
+
+    #[test]
+    fn shift_east_carries_across_words() {
+        let bits = with_set(2, 130, &[(0, 63), (1, 127)]);
+        let shifted = bits.shift(&Direction::East);
+        assert_eq!(shifted, with_set(2, 130, &[(0, 64), (1, 128)]));
+        assert_eq!(shifted, rebuilt(&shifted));
+    }
+
+    #[test]
+    fn shift_west_carries_across_words() {
+        let bits = with_set(2, 130, &[(0, 64), (1, 128)]);
+        let shifted = bits.shift(&Direction::West);
+        assert_eq!(shifted, with_set(2, 130, &[(0, 63), (1, 127)]));
+        assert_eq!(shifted, rebuilt(&shifted));
+    }
+
+    #[test]
+    fn shift_drops_cells_off_the_edge() {
+        let bits = with_set(3, 80, &[(0, 0), (0, 79), (2, 40)]);
+        assert_eq!(
+            bits.shift(&Direction::East),
+            with_set(3, 80, &[(0, 1), (2, 41)])
+        );
+        assert_eq!(
+            bits.shift(&Direction::West),
+            with_set(3, 80, &[(0, 78), (2, 39)])
+        );
+        assert_eq!(bits.shift(&Direction::North), with_set(3, 80, &[(1, 40)]));
+        assert_eq!(
+            bits.shift(&Direction::South),
+            with_set(3, 80, &[(1, 0), (1, 79)])
+        );
+    }
+
+    #[test]
+    fn shift_keeps_columns_in_step() {
+        let bits = with_set(70, 70, &[(0, 0), (63, 64), (64, 63), (69, 69)]);
+        for direction in Direction::all() {
+            let shifted = bits.shift(&direction);
+            assert_eq!(shifted, rebuilt(&shifted), "{direction:?}");
+        }
+    }
+
+    #[test]
+    fn combine_keeps_rows_and_columns_in_step() {
+        let a = with_set(70, 70, &[(0, 0), (5, 64), (66, 3)]);
+        let b = with_set(70, 70, &[(0, 0), (5, 65), (66, 3), (69, 69)]);
+        let xor = &a ^ &b;
+        assert_eq!(xor, with_set(70, 70, &[(5, 64), (5, 65), (69, 69)]));
+        assert_eq!(xor, rebuilt(&xor));
+        assert_eq!(xor.row_diff(5, 0), 2);
+        assert_eq!(xor.col_diff(64, 65), 0);
+        assert!(xor.col_eq(64, 65));
+        assert_eq!(&a & &b, with_set(70, 70, &[(0, 0), (66, 3)]));
+        assert_eq!((&a | &b).count_ones(), 5);
+    }
+}
@@ -0,0 +1,134 @@
+use std::fmt::Display;
+
+use crate::{pos, Grid, GridCell, Position};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CellChange<'a, T> {
+    pub pos: Position,
+    pub old: &'a T,
+    pub new: &'a T,
+}
+
+// the cells that differ between two states of a grid
+#[derive(Clone, Debug)]
+pub struct GridDiff<'a, T> {
+    old: &'a Grid<T>,
+    new: &'a Grid<T>,
+    pub changes: Vec<CellChange<'a, T>>,
+    // cells past the edge of the other grid when the sizes differ
+    pub removed: Vec<Position>,
+    pub added: Vec<Position>,
+}
+
+// compares the area both grids share, a size mismatch also counts as a difference
+pub fn grid_diff<'a, T: PartialEq>(old: &'a Grid<T>, new: &'a Grid<T>) -> GridDiff<'a, T> {
+    let rows = old.rows.min(new.rows);
+    let cols = old.cols.min(new.cols);
+    let changes = (0..rows)
+        .flat_map(|row| (0..cols).map(move |col| pos!(row, col)))
+        .filter(|pos| old[*pos] != new[*pos])
+        .map(|pos| CellChange {
+            pos,
+            old: &old[pos],
+            new: &new[pos],
+        })
+        .collect();
+    let outside = |from: &Grid<T>, other: &Grid<T>| -> Vec<Position> {
+        from.iter()
+            .map(|(pos, _)| pos)
+            .filter(|pos| !other.is_valid_pos(pos))
+            .collect()
+    };
+    GridDiff {
+        old,
+        new,
+        changes,
+        removed: outside(old, new),
+        added: outside(new, old),
+    }
+}
+
+impl<T> GridDiff<'_, T> {
+    pub fn same_size(&self) -> bool {
+        self.old.rows == self.new.rows && self.old.cols == self.new.cols
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.same_size() && self.changes.is_empty()
+    }
+}
+
+// old and new side by side with the changed cells marked by ^, then every change listed
+// along with the cells only one of the grids has
+impl<T: GridCell + PartialEq> Display for GridDiff<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.same_size() {
+            writeln!(
+                f,
+                "size changed from {}x{} to {}x{}",
+                self.old.rows, self.old.cols, self.new.rows, self.new.cols
+            )?;
+        }
+        let differ = self.changes.len() + self.removed.len() + self.added.len();
+        writeln!(f, "{differ} cells differ")?;
+
+        // rows past the end of a grid render as blanks to keep the columns lined up
+        let render = |grid: &Grid<T>, row: usize| -> String {
+            (0..grid.cols)
+                .map(|col| {
+                    grid.try_get(&pos!(row, col))
+                        .map_or(' ', |node| node.to_char())
+                })
+                .collect()
+        };
+        for row in 0..self.old.rows.max(self.new.rows) {
+            let marks: String = (0..self.old.cols.max(self.new.cols))
+                .map(|col| {
+                    let pos = pos!(row, col);
+                    match (self.old.try_get(&pos), self.new.try_get(&pos)) {
+                        (Some(old), Some(new)) if old == new => ' ',
+                        (None, None) => ' ',
+                        _ => '^',
+                    }
+                })
+                .collect();
+            let line = format!(
+                "{} | {} | {}",
+                render(self.old, row),
+                render(self.new, row),
+                marks
+            );
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        for change in &self.changes {
+            writeln!(
+                f,
+                "({}, {}): {} -> {}",
+                change.pos.row,
+                change.pos.col,
+                change.old.to_char(),
+                change.new.to_char()
+            )?;
+        }
+        for pos in &self.removed {
+            writeln!(
+                f,
+                "({}, {}): removed {}",
+                pos.row,
+                pos.col,
+                self.old[*pos].to_char()
+            )?;
+        }
+        for pos in &self.added {
+            writeln!(
+                f,
+                "({}, {}): added {}",
+                pos.row,
+                pos.col,
+                self.new[*pos].to_char()
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -1,7 +1,21 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use core::str::FromStr;
-use lazy_static::lazy_static;
-use std::{collections::HashMap, fmt::Display, fs::read_to_string, ops::Add};
+use std::{
+    collections::{HashSet, VecDeque},
+    fmt::{Debug, Display},
+    fs::read_to_string,
+    ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign},
+};
+
+mod bitgrid;
+mod diff;
+mod sparse;
+mod view;
+
+pub use bitgrid::BitGrid;
+pub use diff::{grid_diff, CellChange, GridDiff};
+pub use sparse::SparseGrid;
+pub use view::GridView;
 
 pub fn read_one_per_line<T>(path: &str) -> Result<Vec<T>>
 where
@@ -13,14 +27,50 @@ where
         .collect())
 }
 
-#[derive(Hash, Debug, Eq, Copy, Clone, PartialEq)]
+// parses every grid in blocks separated by blank lines
+pub fn parse_grids<T: GridCell>(s: &str) -> Result<Vec<Grid<T>>> {
+    let mut blocks: Vec<Vec<&str>> = vec![Vec::new()];
+    for line in s.lines() {
+        if line.trim().is_empty() {
+            if !blocks.last().unwrap().is_empty() {
+                blocks.push(Vec::new());
+            }
+        } else {
+            blocks.last_mut().unwrap().push(line);
+        }
+    }
+    if blocks.last().unwrap().is_empty() {
+        blocks.pop();
+    }
+
+    blocks
+        .iter()
+        .enumerate()
+        .map(|(idx, block)| {
+            block
+                .join("\n")
+                .parse()
+                .map_err(|err| anyhow!("block {idx}: {err}"))
+        })
+        .collect()
+}
+
+// ordering is row-major, by row first and then by column
+#[derive(Hash, Debug, Eq, Copy, Clone, PartialEq, PartialOrd, Ord)]
 pub struct Position {
     pub row: i32,
     pub col: i32,
 }
 
+// pos!(row, col; i64) builds a Position64 instead
 #[macro_export]
 macro_rules! pos {
+    ($row:expr, $col:expr; i64) => {
+        Position64 {
+            row: $row as i64,
+            col: $col as i64,
+        }
+    };
     ($row:expr, $col:expr) => {
         Position {
             row: $row as i32,
@@ -29,107 +79,551 @@ macro_rules! pos {
     };
 }
 
-#[derive(Clone, PartialEq)]
+// a position for puzzles whose coordinates outgrow i32, it has no grid of its own
+#[derive(Hash, Debug, Eq, Copy, Clone, PartialEq, PartialOrd, Ord)]
+pub struct Position64 {
+    pub row: i64,
+    pub col: i64,
+}
+
+impl From<Position> for Position64 {
+    fn from(pos: Position) -> Self {
+        pos!(pos.row, pos.col; i64)
+    }
+}
+
+// ordering compares the dimensions first, then the cells in row-major order
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Grid<T> {
     pub rows: usize,
     pub cols: usize,
-    pub nodes: HashMap<Position, T>,
+    // row-major storage, the cell at (row, col) lives at row * cols + col
+    pub nodes: Vec<T>,
 }
 
-impl<T: std::cmp::PartialEq + std::fmt::Debug> PartialOrd for Grid<T> {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        let other_str = format!("{other}");
-        let me_str = format!("{self}");
-        Some(me_str.cmp(&other_str))
+// a single cell of a puzzle map, written and read back as one character
+pub trait GridCell: Sized {
+    fn from_char(ch: char) -> Result<Self>;
+    fn to_char(&self) -> char;
+}
+
+impl GridCell for char {
+    fn from_char(ch: char) -> Result<Self> {
+        Ok(ch)
+    }
+
+    fn to_char(&self) -> char {
+        *self
+    }
+}
+
+// single digit cells, like heat or height maps
+impl GridCell for u8 {
+    fn from_char(ch: char) -> Result<Self> {
+        ch.to_digit(10)
+            .map(|digit| digit as u8)
+            .ok_or(anyhow!("not a digit"))
+    }
+
+    fn to_char(&self) -> char {
+        // anything past 9 has no digit to print as
+        char::from_digit(*self as u32, 10).unwrap_or('?')
     }
 }
 
-impl<T: std::default::Default + std::convert::From<char>> FromStr for Grid<T> {
+impl<T: GridCell> FromStr for Grid<T> {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> anyhow::Result<Self> {
-        let mut s = s.to_string();
-        s.pop(); // annoying newline character
-        let data = s.split("\n");
-        let rows = data.clone().count();
-        let cols = data.clone().next().unwrap().chars().count();
-        let mut grid: Grid<T> = Grid::new(rows, cols, false);
-        data.enumerate().for_each(|(row, line)| {
-            line.chars().enumerate().for_each(|(col, ch)| {
-                grid.nodes
-                    .insert(pos!(row, col), ch.try_into().expect("unable to parse"));
-            })
-        });
-        Ok(grid)
+        Grid::parse_with(s, |_, ch| T::from_char(ch))
     }
 }
 
-impl<T: std::fmt::Debug> Display for Grid<T> {
+// prints the grid exactly like the puzzle input so it parses back
+impl<T: GridCell> Display for Grid<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f).unwrap();
-        for row in 0..self.rows {
-            for col in 0..self.cols {
-                write!(f, "{:?} ", self.nodes.get(&pos!(row, col)).unwrap()).unwrap();
-            }
-            writeln!(f).unwrap();
+        write!(f, "{}", self.render(T::to_char))
+    }
+}
+
+impl<T> Grid<T> {
+    // one character per cell picked by f, for cells that are not a GridCell
+    pub fn render(&self, f: impl Fn(&T) -> char) -> String {
+        let mut out = String::with_capacity(self.rows * (self.cols + 1));
+        for row in self.rows_iter() {
+            out.extend(row.map(&f));
+            out.push('\n');
         }
-        Ok(())
+        out
     }
 }
 
-impl<T: Default> Grid<T> {
-    pub fn new(rows: usize, cols: usize, prefill: bool) -> Self {
-        let mut nodes = HashMap::new();
-        if prefill {
-            // prefill the HashMap
-            (0..rows)
-                .map(|row| (0..cols).map(move |col| pos!(row as i32, col as i32)))
-                .flatten()
-                .for_each(|pos| {
-                    nodes.insert(pos, T::default());
-                });
+impl<T: Debug> Grid<T> {
+    // every cell's Debug output separated by spaces, for cells wider than one character
+    pub fn render_debug(&self) -> String {
+        let mut out = String::new();
+        for row in self.rows_iter() {
+            let cells: Vec<String> = row.map(|node| format!("{node:?}")).collect();
+            out.push_str(&cells.join(" "));
+            out.push('\n');
         }
+        out
+    }
+}
 
-        Grid { rows, cols, nodes }
+impl<T: Default + Clone> Grid<T> {
+    // every cell starts out as T::default()
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Grid {
+            rows,
+            cols,
+            nodes: vec![T::default(); rows * cols],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    // parses each cell with f, which also gets to see where the cell is
+    pub fn parse_with(s: &str, mut f: impl FnMut(Position, char) -> Result<T>) -> Result<Self> {
+        // lines() takes care of \r\n, trailing newlines are optional
+        let data = s.trim_end_matches(['\n', '\r']).lines();
+        let mut rows = 0;
+        let mut cols = 0;
+        let mut nodes = Vec::new();
+        for (row, line) in data.enumerate() {
+            let mut line_len = 0;
+            for (col, ch) in line.chars().enumerate() {
+                let node = f(pos!(row, col), ch)
+                    .map_err(|err| anyhow!("invalid {ch:?} at row {row}, col {col}: {err}"))?;
+                nodes.push(node);
+                line_len += 1;
+            }
+            if row == 0 {
+                cols = line_len;
+            } else if line_len != cols {
+                return Err(anyhow!(
+                    "row {row} has {line_len} cells, expected {cols} like row 0"
+                ));
+            }
+            rows += 1;
+        }
+        Ok(Grid { rows, cols, nodes })
+    }
+
+    pub fn from_rows(data: Vec<Vec<T>>) -> Result<Self> {
+        let rows = data.len();
+        let cols = data.first().map_or(0, |row| row.len());
+        let mut nodes = Vec::with_capacity(rows * cols);
+        for (row, line) in data.into_iter().enumerate() {
+            if line.len() != cols {
+                return Err(anyhow!(
+                    "row {row} has {} cells, expected {cols} like row 0",
+                    line.len()
+                ));
+            }
+            nodes.extend(line);
+        }
+        Ok(Grid { rows, cols, nodes })
+    }
+
+    fn idx(&self, pos: &Position) -> usize {
+        pos.row as usize * self.cols + pos.col as usize
+    }
+
+    pub fn try_get_mut(&mut self, pos: &Position) -> Option<&mut T> {
+        if !self.is_valid_pos(pos) {
+            return None;
+        }
+        let idx = self.idx(pos);
+        Some(&mut self.nodes[idx])
+    }
+    pub fn try_get(&self, pos: &Position) -> Option<&T> {
+        if !self.is_valid_pos(pos) {
+            return None;
+        }
+        Some(&self.nodes[self.idx(pos)])
     }
 
     pub fn get_mut(&mut self, pos: &Position) -> &mut T {
-        self.nodes
-            .get_mut(pos)
-            .expect(&format!("{:?} is invalid", pos))
+        self.try_get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} is invalid", pos))
     }
     pub fn get(&self, pos: &Position) -> &T {
-        self.nodes.get(pos).expect(&format!("{:?} is invalid", pos))
+        self.try_get(pos)
+            .unwrap_or_else(|| panic!("{:?} is invalid", pos))
     }
 
-    pub fn get_neighbours(&self, me: &Position, diag: bool) -> Vec<Position> {
-        if diag {
-            DIAG_OFFSETS
-                .iter()
-                .map(|offset| me + offset)
-                .filter(|pos| self.is_valid_pos(pos))
-                .collect()
-        } else {
-            OFFSETS
-                .iter()
-                .map(|offset| me + offset)
-                .filter(|pos| self.is_valid_pos(pos))
-                .collect()
-        }
+    pub fn get_neighbours(&self, me: &Position, neighbourhood: &Neighbourhood) -> Vec<Position> {
+        self.neighbours(*me, neighbourhood).collect()
+    }
+
+    // same as get_neighbours without allocating, for hot loops
+    pub fn neighbours<'a>(
+        &'a self,
+        me: Position,
+        neighbourhood: &'a Neighbourhood,
+    ) -> impl Iterator<Item = Position> + 'a {
+        neighbourhood
+            .offsets()
+            .iter()
+            .map(move |offset| me + offset)
+            .filter(|pos| self.is_valid_pos(pos))
+    }
+
+    // orthogonal neighbours tagged with the direction taken to reach them
+    pub fn dir_neighbours(&self, me: Position) -> impl Iterator<Item = (Direction, Position)> + '_ {
+        Direction::all()
+            .into_iter()
+            .map(move |dir| (dir, me + dir.offset()))
+            .filter(|(_, pos)| self.is_valid_pos(pos))
+    }
+
+    // all eight surrounding cells tagged with the compass direction that reaches them
+    pub fn dir8_neighbours(
+        &self,
+        me: Position,
+    ) -> impl Iterator<Item = (Direction8, Position)> + '_ {
+        Direction8::all()
+            .into_iter()
+            .map(move |dir| (dir, me + dir.offset()))
+            .filter(|(_, pos)| self.is_valid_pos(pos))
     }
 
     pub fn get_neighbours_raw(&self, me: &Position) -> Vec<Position> {
-        OFFSETS.iter().map(|offset| me + offset).collect()
+        self.neighbours_raw(*me).collect()
+    }
+
+    // orthogonal neighbours without the bounds check
+    pub fn neighbours_raw(&self, me: Position) -> impl Iterator<Item = Position> {
+        OFFSETS.iter().map(move |offset| me + offset)
+    }
+
+    pub fn get_neighbours_wrapped(
+        &self,
+        me: &Position,
+        neighbourhood: &Neighbourhood,
+    ) -> Vec<Position> {
+        self.neighbours_wrapped(*me, neighbourhood).collect()
     }
+
+    // neighbours on a torus, stepping off one edge comes back in on the opposite one
+    pub fn neighbours_wrapped<'a>(
+        &'a self,
+        me: Position,
+        neighbourhood: &'a Neighbourhood,
+    ) -> impl Iterator<Item = Position> + 'a {
+        neighbourhood
+            .offsets()
+            .iter()
+            .map(move |offset| self.wrap_pos(&(me + offset)))
+    }
+
     pub fn is_valid_pos(&self, pos: &Position) -> bool {
         pos.row >= 0 && pos.row < self.rows as i32 && pos.col >= 0 && pos.col < self.cols as i32
     }
+
+    // maps any position, even a negative one, onto the tile it repeats from
+    pub fn wrap_pos(&self, pos: &Position) -> Position {
+        pos!(
+            pos.row.rem_euclid(self.rows as i32),
+            pos.col.rem_euclid(self.cols as i32)
+        )
+    }
+
+    // looks the grid up as if it was tiled endlessly in every direction
+    pub fn get_wrapped(&self, pos: &Position) -> &T {
+        self.get(&self.wrap_pos(pos))
+    }
+
+    pub fn get_wrapped_mut(&mut self, pos: &Position) -> &mut T {
+        let pos = self.wrap_pos(pos);
+        self.get_mut(&pos)
+    }
+
+    pub fn row_slice(&self, row: usize) -> &[T] {
+        assert!(row < self.rows, "row {row} is invalid");
+        &self.nodes[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn row(&self, row: usize) -> std::slice::Iter<'_, T> {
+        self.row_slice(row).iter()
+    }
+
+    pub fn col(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        assert!(col < self.cols, "col {col} is invalid");
+        self.nodes.iter().skip(col).step_by(self.cols)
+    }
+
+    pub fn rows_iter(&self) -> impl Iterator<Item = std::slice::Iter<'_, T>> {
+        (0..self.rows).map(|row| self.row(row))
+    }
+
+    pub fn cols_iter(
+        &self,
+    ) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T> + ExactSizeIterator> {
+        (0..self.cols).map(|col| self.col(col))
+    }
+
+    // every cell with its position, in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(idx, node)| (pos!(idx / self.cols, idx % self.cols), node))
+    }
+
+    // first matching cell in row-major order
+    pub fn find(&self, mut pred: impl FnMut(&T) -> bool) -> Option<Position> {
+        self.iter().find(|(_, node)| pred(node)).map(|(pos, _)| pos)
+    }
+
+    pub fn count(&self, mut pred: impl FnMut(&T) -> bool) -> usize {
+        self.nodes.iter().filter(|node| pred(node)).count()
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            rows: self.rows,
+            cols: self.cols,
+            nodes: self.nodes.iter().map(f).collect(),
+        }
+    }
+
+    // every cell reachable from start through orthogonal steps onto passable cells
+    pub fn flood_fill(
+        &self,
+        start: Position,
+        mut passable: impl FnMut(&T) -> bool,
+    ) -> HashSet<Position> {
+        let mut reached = HashSet::new();
+        if !self.try_get(&start).is_some_and(&mut passable) {
+            return reached;
+        }
+        let mut queue = VecDeque::from([start]);
+        reached.insert(start);
+        while let Some(pos) = queue.pop_front() {
+            for neighbour in self.neighbours(pos, &Neighbourhood::Orthogonal) {
+                if !reached.contains(&neighbour) && passable(&self[neighbour]) {
+                    reached.insert(neighbour);
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+        reached
+    }
+
+    // splits the grid into orthogonally connected regions where same_region holds between
+    // neighbouring cells, returns each cell's region label and the cells of every region
+    pub fn components(
+        &self,
+        mut same_region: impl FnMut(&T, &T) -> bool,
+    ) -> (Grid<usize>, Vec<Vec<Position>>) {
+        let mut labels = self.map(|_| usize::MAX);
+        let mut regions = Vec::new();
+        for (start, _) in self.iter() {
+            if labels[start] != usize::MAX {
+                continue;
+            }
+            let label = regions.len();
+            let mut region = vec![start];
+            labels[start] = label;
+            let mut queue = VecDeque::from([start]);
+            while let Some(pos) = queue.pop_front() {
+                for neighbour in self.neighbours(pos, &Neighbourhood::Orthogonal) {
+                    if labels[neighbour] == usize::MAX && same_region(&self[pos], &self[neighbour])
+                    {
+                        labels[neighbour] = label;
+                        region.push(neighbour);
+                        queue.push_back(neighbour);
+                    }
+                }
+            }
+            regions.push(region);
+        }
+        (labels, regions)
+    }
+}
+
+// where a ray cast across the grid came to rest
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cast {
+    // last cell the ray could move onto, the starting cell if it never moved
+    pub last: Position,
+    // the cell that stopped it, None when it ran off the edge
+    pub blocker: Option<Position>,
+}
+
+// a maximal horizontal stretch of matching cells in one row
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Run<'a, T> {
+    pub row: usize,
+    // column of the first cell
+    pub start: usize,
+    pub cells: &'a [T],
 }
-pub fn manhattan_distance(from: &Position, to: &Position) -> usize {
-    let x_dist = (to.row - from.row).abs();
-    let y_dist = (to.col - from.col).abs();
-    (x_dist + y_dist) as usize
+
+impl<T> Run<'_, T> {
+    // column just past the last cell
+    pub fn end(&self) -> usize {
+        self.start + self.cells.len()
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let row = self.row;
+        (self.start..self.end()).map(move |col| pos!(row, col))
+    }
+}
+
+impl<T> Grid<T> {
+    // every maximal horizontal run of cells matching pred, row by row
+    pub fn runs(&self, mut pred: impl FnMut(&T) -> bool) -> Vec<Run<'_, T>> {
+        let mut runs = Vec::new();
+        for row in 0..self.rows {
+            let cells = self.row_slice(row);
+            let mut start = None;
+            for col in 0..=cells.len() {
+                match (start, col < cells.len() && pred(&cells[col])) {
+                    (None, true) => start = Some(col),
+                    (Some(first), false) => {
+                        runs.push(Run {
+                            row,
+                            start: first,
+                            cells: &cells[first..col],
+                        });
+                        start = None;
+                    }
+                    _ => (),
+                }
+            }
+        }
+        runs
+    }
+
+    // which of the runs, by index, each cell belongs to
+    pub fn run_index(&self, runs: &[Run<'_, T>]) -> Grid<Option<usize>> {
+        let mut index = self.map(|_| None);
+        for (idx, run) in runs.iter().enumerate() {
+            run.positions().for_each(|pos| index[pos] = Some(idx));
+        }
+        index
+    }
+}
+
+impl<T> Grid<T> {
+    // every cell past from in a straight line, up to the edge of the grid
+    pub fn ray(
+        &self,
+        from: Position,
+        direction: &Direction,
+    ) -> impl Iterator<Item = (Position, &T)> {
+        let offset = direction.offset();
+        std::iter::successors(Some(from + offset), move |pos| Some(*pos + offset))
+            .map_while(|pos| self.try_get(&pos).map(|node| (pos, node)))
+    }
+
+    // walks from the start until stop matches a cell or the ray leaves the grid
+    pub fn cast(
+        &self,
+        from: Position,
+        direction: &Direction,
+        mut stop: impl FnMut(&T) -> bool,
+    ) -> Cast {
+        let mut last = from;
+        for (pos, node) in self.ray(from, direction) {
+            if stop(node) {
+                return Cast {
+                    last,
+                    blocker: Some(pos),
+                };
+            }
+            last = pos;
+        }
+        Cast {
+            last,
+            blocker: None,
+        }
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    pub fn positions_of<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Position> + 'a {
+        self.iter()
+            .filter(move |(_, node)| *node == value)
+            .map(|(pos, _)| pos)
+    }
 }
+
+impl<T: Clone> Grid<T> {
+    // out-of-bounds positions read as the default instead of panicking
+    pub fn get_or(&self, pos: &Position, default: T) -> T {
+        self.try_get(pos).cloned().unwrap_or(default)
+    }
+
+    // builds a rows x cols grid where each new (row, col) copies the old cell at source(row, col)
+    fn remap(
+        &self,
+        rows: usize,
+        cols: usize,
+        source: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Self {
+        let nodes = (0..rows)
+            .flat_map(|row| (0..cols).map(move |col| (row, col)))
+            .map(|(row, col)| {
+                let (old_row, old_col) = source(row, col);
+                self.nodes[old_row * self.cols + old_col].clone()
+            })
+            .collect();
+        Grid { rows, cols, nodes }
+    }
+
+    pub fn transpose(&self) -> Self {
+        self.remap(self.cols, self.rows, |row, col| (col, row))
+    }
+
+    pub fn rotate_cw(&self) -> Self {
+        self.remap(self.cols, self.rows, |row, col| (self.rows - 1 - col, row))
+    }
+
+    pub fn rotate_ccw(&self) -> Self {
+        self.remap(self.cols, self.rows, |row, col| (col, self.cols - 1 - row))
+    }
+
+    // mirrors left to right, each row is reversed
+    pub fn flip_horizontal(&self) -> Self {
+        self.remap(self.rows, self.cols, |row, col| (row, self.cols - 1 - col))
+    }
+
+    // mirrors top to bottom, the row order is reversed
+    pub fn flip_vertical(&self) -> Self {
+        self.remap(self.rows, self.cols, |row, col| (self.rows - 1 - row, col))
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Position) -> &Self::Output {
+        self.get(&pos)
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, pos: Position) -> &mut Self::Output {
+        self.get_mut(&pos)
+    }
+}
+
+// what manhattan_distance needs from a position, whatever its width
+pub trait Coordinate {
+    type Distance;
+
+    fn manhattan_distance(&self, other: &Self) -> Self::Distance;
+}
+
+pub fn manhattan_distance<P: Coordinate>(from: &P, to: &P) -> P::Distance {
+    from.manhattan_distance(to)
+}
+
 pub const OFFSETS: [Position; 4] = [
     pos!(0, 1),  // right
     pos!(0, -1), // left
@@ -138,35 +632,142 @@ pub const OFFSETS: [Position; 4] = [
 ];
 
 const DIAG_OFFSETS: [Position; 4] = [
-    pos!(1, -1),  // top right
+    pos!(1, -1),  // bottom left
     pos!(-1, -1), // top left
     pos!(1, 1),   // bottom right
-    pos!(-1, 1),  // bottom left
+    pos!(-1, 1),  // top right
 ];
 
-impl Add<&Position> for &Position {
-    type Output = Position;
+const MOORE_OFFSETS: [Position; 8] = [
+    OFFSETS[0],
+    OFFSETS[1],
+    OFFSETS[2],
+    OFFSETS[3],
+    DIAG_OFFSETS[0],
+    DIAG_OFFSETS[1],
+    DIAG_OFFSETS[2],
+    DIAG_OFFSETS[3],
+];
 
-    fn add(self, rhs: &Position) -> Self::Output {
-        Position {
-            row: self.row + rhs.row,
-            col: self.col + rhs.col,
+// which cells around a position count as its neighbours
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Neighbourhood {
+    Orthogonal,
+    Diagonal,
+    // all eight surrounding cells
+    Moore,
+    Custom(Vec<Position>),
+}
+
+impl Neighbourhood {
+    pub fn offsets(&self) -> &[Position] {
+        match self {
+            Neighbourhood::Orthogonal => &OFFSETS,
+            Neighbourhood::Diagonal => &DIAG_OFFSETS,
+            Neighbourhood::Moore => &MOORE_OFFSETS,
+            Neighbourhood::Custom(offsets) => offsets,
         }
     }
 }
 
-impl Add<&Position> for Position {
-    type Output = Position;
+// the same arithmetic for every coordinate width
+macro_rules! impl_position_ops {
+    ($pos:ident, $int:ty, $dist:ty) => {
+        impl Coordinate for $pos {
+            type Distance = $dist;
 
-    fn add(self, rhs: &Position) -> Self::Output {
-        Position {
-            row: self.row + rhs.row,
-            col: self.col + rhs.col,
+            fn manhattan_distance(&self, other: &$pos) -> $dist {
+                (self.row.abs_diff(other.row) + self.col.abs_diff(other.col)) as $dist
+            }
         }
-    }
+
+        impl Add<&$pos> for &$pos {
+            type Output = $pos;
+
+            fn add(self, rhs: &$pos) -> Self::Output {
+                *self + *rhs
+            }
+        }
+
+        impl Add<&$pos> for $pos {
+            type Output = $pos;
+
+            fn add(self, rhs: &$pos) -> Self::Output {
+                self + *rhs
+            }
+        }
+
+        impl Add for $pos {
+            type Output = $pos;
+
+            fn add(self, rhs: $pos) -> Self::Output {
+                $pos {
+                    row: self.row + rhs.row,
+                    col: self.col + rhs.col,
+                }
+            }
+        }
+
+        impl Sub for $pos {
+            type Output = $pos;
+
+            fn sub(self, rhs: $pos) -> Self::Output {
+                $pos {
+                    row: self.row - rhs.row,
+                    col: self.col - rhs.col,
+                }
+            }
+        }
+
+        impl Sub<&$pos> for &$pos {
+            type Output = $pos;
+
+            fn sub(self, rhs: &$pos) -> Self::Output {
+                *self - *rhs
+            }
+        }
+
+        impl Neg for $pos {
+            type Output = $pos;
+
+            fn neg(self) -> Self::Output {
+                $pos {
+                    row: -self.row,
+                    col: -self.col,
+                }
+            }
+        }
+
+        // scales an offset, so n steps along a direction is pos + offset * n
+        impl Mul<$int> for $pos {
+            type Output = $pos;
+
+            fn mul(self, rhs: $int) -> Self::Output {
+                $pos {
+                    row: self.row * rhs,
+                    col: self.col * rhs,
+                }
+            }
+        }
+
+        impl AddAssign for $pos {
+            fn add_assign(&mut self, rhs: $pos) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl SubAssign for $pos {
+            fn sub_assign(&mut self, rhs: $pos) {
+                *self = *self - rhs;
+            }
+        }
+    };
 }
 
-#[derive(Eq, PartialEq, Debug, Clone, Hash)]
+impl_position_ops!(Position, i32, usize);
+impl_position_ops!(Position64, i64, u64);
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Hash, PartialOrd, Ord)]
 pub enum Direction {
     North,
     South,
@@ -174,12 +775,209 @@ pub enum Direction {
     West,
 }
 
-lazy_static! {
-    pub static ref DIR_OFFSETS: HashMap<Direction, Position> = [
-        (Direction::East, OFFSETS[0]),
-        (Direction::West, OFFSETS[1]),
-        (Direction::North, OFFSETS[2]),
-        (Direction::South, OFFSETS[3]),
-    ]
-    .into();
+impl Direction {
+    // in the same order as OFFSETS
+    pub const fn all() -> [Direction; 4] {
+        [
+            Direction::East,
+            Direction::West,
+            Direction::North,
+            Direction::South,
+        ]
+    }
+
+    pub const fn offset(&self) -> Position {
+        match self {
+            Direction::East => OFFSETS[0],
+            Direction::West => OFFSETS[1],
+            Direction::North => OFFSETS[2],
+            Direction::South => OFFSETS[3],
+        }
+    }
+
+    pub const fn opposite(&self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::East => Direction::West,
+            Direction::West => Direction::East,
+        }
+    }
+
+    // clockwise
+    pub const fn turn_right(&self) -> Direction {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    // counter-clockwise
+    pub const fn turn_left(&self) -> Direction {
+        self.turn_right().opposite()
+    }
+
+    pub fn from_notation(ch: char, notation: Notation) -> Result<Direction> {
+        Direction::all()
+            .into_iter()
+            .find(|dir| dir.to_notation(notation) == ch)
+            .ok_or(anyhow!(
+                "{ch:?} is not a direction in {notation:?} notation"
+            ))
+    }
+
+    pub const fn to_notation(&self, notation: Notation) -> char {
+        match (notation, self) {
+            (Notation::Udlr, Direction::North) => 'U',
+            (Notation::Udlr, Direction::South) => 'D',
+            (Notation::Udlr, Direction::East) => 'R',
+            (Notation::Udlr, Direction::West) => 'L',
+            (Notation::Compass, Direction::North) => 'N',
+            (Notation::Compass, Direction::South) => 'S',
+            (Notation::Compass, Direction::East) => 'E',
+            (Notation::Compass, Direction::West) => 'W',
+            (Notation::Arrows, Direction::North) => '^',
+            (Notation::Arrows, Direction::South) => 'v',
+            (Notation::Arrows, Direction::East) => '>',
+            (Notation::Arrows, Direction::West) => '<',
+            (Notation::Numeric, Direction::East) => '0',
+            (Notation::Numeric, Direction::South) => '1',
+            (Notation::Numeric, Direction::West) => '2',
+            (Notation::Numeric, Direction::North) => '3',
+        }
+    }
+}
+
+// the eight compass points, declared clockwise from north
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Hash, PartialOrd, Ord)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    pub const fn all() -> [Direction8; 8] {
+        [
+            Direction8::North,
+            Direction8::NorthEast,
+            Direction8::East,
+            Direction8::SouthEast,
+            Direction8::South,
+            Direction8::SouthWest,
+            Direction8::West,
+            Direction8::NorthWest,
+        ]
+    }
+
+    pub const fn offset(&self) -> Position {
+        match self {
+            Direction8::North => OFFSETS[2],
+            Direction8::NorthEast => DIAG_OFFSETS[3],
+            Direction8::East => OFFSETS[0],
+            Direction8::SouthEast => DIAG_OFFSETS[2],
+            Direction8::South => OFFSETS[3],
+            Direction8::SouthWest => DIAG_OFFSETS[0],
+            Direction8::West => OFFSETS[1],
+            Direction8::NorthWest => DIAG_OFFSETS[1],
+        }
+    }
+
+    // turns clockwise in 45 degree steps, negative steps turn counter-clockwise
+    pub const fn rotate(&self, steps: i32) -> Direction8 {
+        let idx = (*self as i32 + steps).rem_euclid(8);
+        Direction8::all()[idx as usize]
+    }
+
+    pub const fn opposite(&self) -> Direction8 {
+        self.rotate(4)
+    }
+
+    pub const fn is_diagonal(&self) -> bool {
+        *self as usize % 2 == 1
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(dir: Direction) -> Self {
+        match dir {
+            Direction::North => Direction8::North,
+            Direction::South => Direction8::South,
+            Direction::East => Direction8::East,
+            Direction::West => Direction8::West,
+        }
+    }
+}
+
+impl TryFrom<Direction8> for Direction {
+    type Error = anyhow::Error;
+
+    fn try_from(dir: Direction8) -> Result<Self> {
+        match dir {
+            Direction8::North => Ok(Direction::North),
+            Direction8::South => Ok(Direction::South),
+            Direction8::East => Ok(Direction::East),
+            Direction8::West => Ok(Direction::West),
+            _ => Err(anyhow!("{dir:?} is diagonal")),
+        }
+    }
+}
+
+// the ways puzzles spell out a direction as a single character
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Notation {
+    // U, D, L and R
+    Udlr,
+    // N, S, E and W
+    Compass,
+    // ^, v, < and >
+    Arrows,
+    // 0 to 3 going clockwise from east, like day 18's hex codes
+    Numeric,
+}
+
+const NOTATIONS: [Notation; 4] = [
+    Notation::Udlr,
+    Notation::Compass,
+    Notation::Arrows,
+    Notation::Numeric,
+];
+
+// the notations share no characters, so any of them is accepted
+impl TryFrom<char> for Direction {
+    type Error = anyhow::Error;
+
+    fn try_from(ch: char) -> Result<Self> {
+        NOTATIONS
+            .into_iter()
+            .find_map(|notation| Direction::from_notation(ch, notation).ok())
+            .ok_or(anyhow!("{ch:?} is not a direction"))
+    }
+}
+
+// a single character in any notation, or the name of a direction
+impl FromStr for Direction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let mut chars = s.chars();
+        if let (Some(ch), None) = (chars.next(), chars.next()) {
+            return Direction::try_from(ch);
+        }
+        match s.to_lowercase().as_str() {
+            "north" | "up" => Ok(Direction::North),
+            "south" | "down" => Ok(Direction::South),
+            "east" | "right" => Ok(Direction::East),
+            "west" | "left" => Ok(Direction::West),
+            _ => Err(anyhow!("{s:?} is not a direction")),
+        }
+    }
 }
@@ -2,6 +2,6 @@ use aoc::Grid;
 
 fn main() {
     println!("Hello, world!");
-    let grid: Grid<usize> = Grid::new(5, 5, true);
-    println!("{grid}");
+    let grid: Grid<usize> = Grid::new(5, 5);
+    println!("{}", grid.render_debug());
 }
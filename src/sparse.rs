@@ -0,0 +1,99 @@
+use std::{collections::HashMap, fmt::Display};
+
+use crate::{pos, Grid, GridCell, Position};
+
+// an unbounded grid that only stores the cells that were written to,
+// positions may go negative and every other cell reads as the default
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    pub nodes: HashMap<Position, T>,
+    default: T,
+    // top left and bottom right corners of everything written so far
+    bounds: Option<(Position, Position)>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(default: T) -> Self {
+        SparseGrid {
+            nodes: HashMap::new(),
+            default,
+            bounds: None,
+        }
+    }
+
+    fn grow(&mut self, pos: &Position) {
+        self.bounds = Some(match self.bounds {
+            None => (*pos, *pos),
+            Some((min, max)) => (
+                pos!(min.row.min(pos.row), min.col.min(pos.col)),
+                pos!(max.row.max(pos.row), max.col.max(pos.col)),
+            ),
+        });
+    }
+
+    pub fn insert(&mut self, pos: Position, value: T) -> Option<T> {
+        self.grow(&pos);
+        self.nodes.insert(pos, value)
+    }
+
+    pub fn get(&self, pos: &Position) -> &T {
+        self.nodes.get(pos).unwrap_or(&self.default)
+    }
+
+    pub fn contains(&self, pos: &Position) -> bool {
+        self.nodes.contains_key(pos)
+    }
+
+    pub fn bounds(&self) -> Option<(Position, Position)> {
+        self.bounds
+    }
+
+    pub fn rows(&self) -> usize {
+        self.bounds
+            .map_or(0, |(min, max)| (max.row - min.row + 1) as usize)
+    }
+
+    pub fn cols(&self) -> usize {
+        self.bounds
+            .map_or(0, |(min, max)| (max.col - min.col + 1) as usize)
+    }
+
+    // every cell inside the bounding box in row-major order, unset cells read as the default
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        let (min, max) = self.bounds.unwrap_or((pos!(0, 0), pos!(-1, -1)));
+        (min.row..=max.row)
+            .flat_map(move |row| (min.col..=max.col).map(move |col| pos!(row, col)))
+            .map(|pos| (pos, self.get(&pos)))
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    // writes the default into unset cells so they can be changed in place
+    pub fn get_mut(&mut self, pos: &Position) -> &mut T {
+        self.grow(pos);
+        self.nodes
+            .entry(*pos)
+            .or_insert_with(|| self.default.clone())
+    }
+
+    // copies the bounding box into a dense grid, its top left corner becomes (0, 0)
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid {
+            rows: self.rows(),
+            cols: self.cols(),
+            nodes: self.iter().map(|(_, node)| node.clone()).collect(),
+        }
+    }
+}
+
+impl<T: GridCell> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (pos, node) in self.iter() {
+            write!(f, "{}", node.to_char())?;
+            if pos.col == self.bounds.unwrap().1.col {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use std::fmt::Display;
+
+use crate::{pos, Grid, GridCell, Position};
+
+// a borrowed rectangle of a grid, positions are relative to its top left corner
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    pub top_left: Position,
+    pub rows: usize,
+    pub cols: usize,
+}
+
+// written out by hand since a derive would require T: Clone, the view only holds a reference
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<T> Grid<T> {
+    pub fn view(&self, top_left: Position, rows: usize, cols: usize) -> GridView<'_, T> {
+        assert!(
+            top_left.row >= 0
+                && top_left.col >= 0
+                && top_left.row as usize + rows <= self.rows
+                && top_left.col as usize + cols <= self.cols,
+            "{rows}x{cols} at {:?} does not fit in the grid",
+            top_left
+        );
+        GridView {
+            grid: self,
+            top_left,
+            rows,
+            cols,
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn subgrid(&self, top_left: Position, rows: usize, cols: usize) -> Grid<T> {
+        self.view(top_left, rows, cols).to_grid()
+    }
+}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn is_valid_pos(&self, pos: &Position) -> bool {
+        pos.row >= 0 && pos.row < self.rows as i32 && pos.col >= 0 && pos.col < self.cols as i32
+    }
+
+    // position of a view cell in the underlying grid
+    pub fn to_grid_pos(&self, pos: &Position) -> Position {
+        self.top_left + pos
+    }
+
+    pub fn try_get(&self, pos: &Position) -> Option<&'a T> {
+        if !self.is_valid_pos(pos) {
+            return None;
+        }
+        self.grid.try_get(&self.to_grid_pos(pos))
+    }
+
+    pub fn get(&self, pos: &Position) -> &'a T {
+        self.try_get(pos)
+            .unwrap_or_else(|| panic!("{:?} is invalid", pos))
+    }
+
+    pub fn row_slice(&self, row: usize) -> &'a [T] {
+        assert!(row < self.rows, "row {row} is invalid");
+        let col = self.top_left.col as usize;
+        &self.grid.row_slice(self.top_left.row as usize + row)[col..col + self.cols]
+    }
+
+    pub fn rows_iter(&self) -> impl Iterator<Item = &'a [T]> + '_ {
+        (0..self.rows).map(|row| self.row_slice(row))
+    }
+
+    // every cell with its view position, in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &'a T)> + '_ {
+        self.rows_iter().enumerate().flat_map(|(row, cells)| {
+            cells
+                .iter()
+                .enumerate()
+                .map(move |(col, node)| (pos!(row, col), node))
+        })
+    }
+}
+
+impl<T: Clone> GridView<'_, T> {
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid {
+            rows: self.rows,
+            cols: self.cols,
+            nodes: self.rows_iter().flatten().cloned().collect(),
+        }
+    }
+}
+
+impl<T: GridCell> Display for GridView<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows_iter() {
+            for node in row {
+                write!(f, "{}", node.to_char())?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}